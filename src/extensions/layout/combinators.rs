@@ -0,0 +1,224 @@
+//! Combinators for composing together simpler layouts into more complex ones.
+use crate::{
+    core::layout::{Layout, Message},
+    extensions::layout::SplitDirection,
+    pure::{geometry::Rect, Stack},
+    Xid,
+};
+
+/// Delegate to one of two child [Layout]s depending on a predicate evaluated against the
+/// current [Stack] and [Rect] being laid out.
+///
+/// This lets you swap between layouts based on, say, the number of clients present on a
+/// workspace, without needing to write a bespoke [Layout] impl of your own.
+pub struct Conditional {
+    name: String,
+    predicate: fn(&Stack<Xid>, Rect) -> bool,
+    if_true: Box<dyn Layout>,
+    if_false: Box<dyn Layout>,
+}
+
+impl Conditional {
+    /// Create a new [Conditional] layout with the given `name`, `predicate` and child
+    /// layouts to delegate to.
+    pub fn new(
+        name: impl Into<String>,
+        predicate: fn(&Stack<Xid>, Rect) -> bool,
+        if_true: Box<dyn Layout>,
+        if_false: Box<dyn Layout>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            predicate,
+            if_true,
+            if_false,
+        }
+    }
+
+    /// Create a new [Conditional] layout as with `new` but returned as a trait object
+    /// ready to be added to your layout stack.
+    pub fn boxed(
+        name: impl Into<String>,
+        predicate: fn(&Stack<Xid>, Rect) -> bool,
+        if_true: Box<dyn Layout>,
+        if_false: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(Self::new(name, predicate, if_true, if_false))
+    }
+}
+
+impl Layout for Conditional {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Layout> {
+        Box::new(Self {
+            name: self.name.clone(),
+            predicate: self.predicate,
+            if_true: self.if_true.boxed_clone(),
+            if_false: self.if_false.boxed_clone(),
+        })
+    }
+
+    fn layout(&mut self, s: &Stack<Xid>, r: Rect) -> (Option<Box<dyn Layout>>, Vec<(Xid, Rect)>) {
+        if (self.predicate)(s, r) {
+            self.if_true.layout(s, r)
+        } else {
+            self.if_false.layout(s, r)
+        }
+    }
+
+    fn handle_message(&mut self, m: &Message) -> Option<Box<dyn Layout>> {
+        if let Some(new_layout) = self.if_true.handle_message(m) {
+            self.if_true = new_layout;
+        }
+        if let Some(new_layout) = self.if_false.handle_message(m) {
+            self.if_false = new_layout;
+        }
+
+        None
+    }
+}
+
+/// A single child slot in a [SplitLayout] node: a [Layout] together with the proportion of
+/// clients it should receive relative to its sibling.
+///
+/// Nesting split trees is simply a matter of giving a [SplitChild] a `layout` that is
+/// itself a [SplitLayout], since [SplitLayout] implements [Layout] the same as any leaf.
+pub struct SplitChild {
+    layout: Box<dyn Layout>,
+    weight: u32,
+}
+
+impl SplitChild {
+    /// Create a new [SplitChild] wrapping `layout`, weighted by `weight` relative to its
+    /// sibling when the parent [SplitLayout] partitions clients between the two of them.
+    pub fn new(layout: Box<dyn Layout>, weight: u32) -> Self {
+        Self {
+            layout,
+            weight: weight.max(1),
+        }
+    }
+}
+
+fn stack_from_ids(ids: &[Xid]) -> Option<Stack<Xid>> {
+    let (&focus, rest) = ids.split_first()?;
+
+    Some(Stack::new(Vec::new(), focus, rest.to_vec()))
+}
+
+/// A combinator that recursively divides a [Rect] between two child layouts, mirroring the
+/// nested horizontal/vertical split trees used by tiling terminal multiplexers.
+///
+/// The incoming [Rect] is split according to `direction` and `ratio` (reusing the same
+/// [Rect::split_at_width_perc] / [Rect::split_at_height_perc] helpers that [Fibonacci]
+/// uses), and the incoming [Stack] is partitioned across the two children in the
+/// same proportion as their [SplitChild] weights, so that e.g. a weight-2 child receives
+/// twice as many clients as a weight-1 sibling. Each child then lays out its own slice of
+/// clients within its own sub-[Rect], and the results are concatenated.
+///
+/// Because [SplitLayout] implements [Layout], a [SplitChild] can itself wrap another
+/// [SplitLayout], letting you build arbitrarily deep split trees, e.g. [Fibonacci] on the
+/// left half and [Tatami] on the right:
+///
+/// ```ignore
+/// SplitLayout::boxed(
+///     SplitDirection::Horizontal,
+///     0.5,
+///     SplitChild::new(Fibonacci::boxed_default(), 1),
+///     SplitChild::new(Tatami::boxed(0.6, 0.1), 1),
+/// )
+/// ```
+///
+/// [Fibonacci]: super::Fibonacci
+/// [Tatami]: super::Tatami
+pub struct SplitLayout {
+    direction: SplitDirection,
+    ratio: f32,
+    first: SplitChild,
+    second: SplitChild,
+}
+
+impl SplitLayout {
+    /// Create a new [SplitLayout] splitting `direction`-wise at `ratio` between `first` and
+    /// `second`. `ratio` is clamped to `0.0..=1.0`; a non-finite `ratio` (e.g. `NaN`) is
+    /// treated as `0.5` instead, since `clamp` alone would otherwise let it through unchanged.
+    pub fn new(direction: SplitDirection, ratio: f32, first: SplitChild, second: SplitChild) -> Self {
+        let ratio = if ratio.is_finite() { ratio } else { 0.5 };
+
+        Self {
+            direction,
+            ratio: ratio.clamp(0.0, 1.0),
+            first,
+            second,
+        }
+    }
+
+    /// Create a new [SplitLayout] as with `new` but returned as a trait object ready to be
+    /// added to your layout stack.
+    pub fn boxed(
+        direction: SplitDirection,
+        ratio: f32,
+        first: SplitChild,
+        second: SplitChild,
+    ) -> Box<dyn Layout> {
+        Box::new(Self::new(direction, ratio, first, second))
+    }
+}
+
+impl Layout for SplitLayout {
+    fn name(&self) -> String {
+        "Split".to_string()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Layout> {
+        Box::new(Self {
+            direction: self.direction,
+            ratio: self.ratio,
+            first: SplitChild::new(self.first.layout.boxed_clone(), self.first.weight),
+            second: SplitChild::new(self.second.layout.boxed_clone(), self.second.weight),
+        })
+    }
+
+    fn layout(&mut self, s: &Stack<Xid>, r: Rect) -> (Option<Box<dyn Layout>>, Vec<(Xid, Rect)>) {
+        let (r1, r2) = match self.direction {
+            SplitDirection::Horizontal => r
+                .split_at_width_perc(self.ratio)
+                .expect("self.ratio is clamped to 0.0..=1.0 in SplitLayout::new"),
+            SplitDirection::Vertical => r
+                .split_at_height_perc(self.ratio)
+                .expect("self.ratio is clamped to 0.0..=1.0 in SplitLayout::new"),
+        };
+
+        let ids: Vec<Xid> = s.iter().copied().collect();
+        let total_weight = self.first.weight + self.second.weight;
+        let split_at = ((ids.len() * self.first.weight as usize) / total_weight as usize).min(ids.len());
+        let (first_ids, second_ids) = ids.split_at(split_at);
+
+        let mut positions = Vec::new();
+        if let Some(stack) = stack_from_ids(first_ids) {
+            positions.extend(self.first.layout.layout(&stack, r1).1);
+        }
+        if let Some(stack) = stack_from_ids(second_ids) {
+            positions.extend(self.second.layout.layout(&stack, r2).1);
+        }
+
+        (None, positions)
+    }
+
+    fn handle_message(&mut self, m: &Message) -> Option<Box<dyn Layout>> {
+        let mut changed = false;
+
+        if let Some(new_layout) = self.first.layout.handle_message(m) {
+            self.first.layout = new_layout;
+            changed = true;
+        }
+        if let Some(new_layout) = self.second.layout.handle_message(m) {
+            self.second.layout = new_layout;
+            changed = true;
+        }
+
+        changed.then(|| self.boxed_clone())
+    }
+}
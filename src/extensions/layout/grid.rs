@@ -0,0 +1,217 @@
+//! A grid layout with an independent, per-boundary resize ratio, plus the messages used to
+//! grow and shrink individual panes rather than just the shared main/secondary ratio.
+use crate::{
+    core::layout::{Layout, Message},
+    extensions::layout::SplitDirection,
+    pure::{geometry::Rect, Stack},
+    Xid,
+};
+
+/// Which of the focused client's neighbours a [Grow] or [Shrink] message should act against.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResizeTarget {
+    /// The neighbour positioned before the focused client.
+    Previous,
+    /// The neighbour positioned after the focused client.
+    Next,
+}
+
+/// Grow the focused client's region along its resizable axis, shrinking the neighbour on
+/// the given [ResizeTarget] side to compensate. If that neighbour is already at the
+/// configured minimum size, the shrink is absorbed by the next neighbour in line instead.
+#[derive(Debug, Copy, Clone)]
+pub struct Grow(pub ResizeTarget);
+
+/// Shrink the focused client's region along its resizable axis, growing the neighbour on
+/// the given [ResizeTarget] side to compensate. This is the inverse of [Grow].
+#[derive(Debug, Copy, Clone)]
+pub struct Shrink(pub ResizeTarget);
+
+/// A single axis grid layout where every boundary between neighbouring clients has its own
+/// independent split ratio, rather than the single shared ratio used by [ExpandMain] and
+/// [ShrinkMain] elsewhere in this module.
+///
+/// Sending [Grow] or [Shrink] resizes the currently focused client against one of its
+/// neighbours: the shared boundary is nudged by a configurable step, with any shrink that
+/// would take a neighbour below `min_percent` of the axis absorbed by the next neighbour
+/// along, rather than stopping dead at the first boundary. This gives dwm/zellij-style
+/// incremental resizing of arbitrary panes, not just the main area.
+///
+/// [ExpandMain]: crate::builtin::layout::messages::ExpandMain
+/// [ShrinkMain]: crate::builtin::layout::messages::ShrinkMain
+#[derive(Debug, Clone)]
+pub struct ResizableGrid {
+    direction: SplitDirection,
+    // Boundary `i` is the fraction of the axis (in 0.0..=1.0) separating client `i` from
+    // client `i + 1`. Re-derived from scratch whenever the client count changes, since
+    // there is no way to know which existing boundary a newly added client should split.
+    boundaries: Vec<f32>,
+    min_percent: f32,
+    step: f32,
+    focus_idx: usize,
+}
+
+impl Default for ResizableGrid {
+    fn default() -> Self {
+        Self {
+            direction: SplitDirection::Horizontal,
+            boundaries: Vec::new(),
+            min_percent: 0.05,
+            step: 0.05,
+            focus_idx: 0,
+        }
+    }
+}
+
+impl ResizableGrid {
+    /// Create a new [ResizableGrid] splitting along `direction`.
+    pub fn new(direction: SplitDirection) -> Self {
+        Self {
+            direction,
+            ..Self::default()
+        }
+    }
+
+    /// Create a new [ResizableGrid] as with `new` but returned as a trait object ready to
+    /// be added to your layout stack.
+    pub fn boxed(direction: SplitDirection) -> Box<dyn Layout> {
+        Box::new(Self::new(direction))
+    }
+
+    /// Set the fraction of the axis that each [Grow] or [Shrink] moves a boundary by, and
+    /// the minimum fraction of the axis that a client can be shrunk to.
+    pub fn with_step(mut self, step: f32, min_percent: f32) -> Self {
+        self.step = step;
+        self.min_percent = min_percent;
+        self
+    }
+
+    fn sync_boundaries(&mut self, n: usize) {
+        let needed = n.saturating_sub(1);
+        if self.boundaries.len() != needed {
+            self.boundaries = (1..=needed).map(|i| i as f32 / n as f32).collect();
+        }
+    }
+
+    // Move the boundary at `idx` by `delta` (positive grows the client before the boundary,
+    // negative grows the client after it). Once the immediate neighbour is at `min_percent`,
+    // the run of boundaries from `idx` onwards moves together, so the rest of the change
+    // comes out of the next neighbour along while the squeezed neighbours keep their size.
+    fn move_boundary(&mut self, idx: usize, mut delta: f32) {
+        let last = match self.boundaries.len().checked_sub(1) {
+            Some(last) => last,
+            None => return,
+        };
+        let mut i = idx;
+
+        while delta.abs() > f32::EPSILON {
+            if delta > 0.0 {
+                let next_bound = if i == last { 1.0 } else { self.boundaries[i + 1] };
+                let room = (next_bound - self.min_percent - self.boundaries[i]).max(0.0);
+                let take = delta.min(room);
+                for b in &mut self.boundaries[idx..=i] {
+                    *b += take;
+                }
+                delta -= take;
+                if delta <= f32::EPSILON || i == last {
+                    break;
+                }
+                i += 1;
+            } else {
+                let prev_bound = if i == 0 { 0.0 } else { self.boundaries[i - 1] };
+                let room = (self.boundaries[i] - self.min_percent - prev_bound).max(0.0);
+                let take = delta.abs().min(room);
+                for b in &mut self.boundaries[i..=idx] {
+                    *b -= take;
+                }
+                delta += take;
+                if delta.abs() <= f32::EPSILON || i == 0 {
+                    break;
+                }
+                i -= 1;
+            }
+        }
+    }
+
+    fn resize_focused(&mut self, target: ResizeTarget, grow: bool) {
+        let delta = if grow { self.step } else { -self.step };
+
+        match target {
+            ResizeTarget::Next if self.focus_idx < self.boundaries.len() => {
+                self.move_boundary(self.focus_idx, delta);
+            }
+            ResizeTarget::Previous if self.focus_idx > 0 => {
+                self.move_boundary(self.focus_idx - 1, -delta);
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Layout for ResizableGrid {
+    fn name(&self) -> String {
+        "ResizableGrid".to_string()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Layout> {
+        Box::new(self.clone())
+    }
+
+    fn layout(&mut self, s: &Stack<Xid>, r: Rect) -> (Option<Box<dyn Layout>>, Vec<(Xid, Rect)>) {
+        let ids: Vec<Xid> = s.iter().copied().collect();
+        let n = ids.len();
+        if n == 0 {
+            return (None, vec![]);
+        }
+
+        self.sync_boundaries(n);
+        self.focus_idx = ids.iter().position(|&id| id == s.focus).unwrap_or(0);
+
+        let axis_len = match self.direction {
+            SplitDirection::Horizontal => r.w,
+            SplitDirection::Vertical => r.h,
+        };
+
+        // Convert the fractional boundaries to integer pixel edges up front, cumulatively,
+        // rather than rounding each pane's start/end independently: that way neighbouring
+        // panes always share an exact edge and the final edge always lands on `axis_len`,
+        // the same gap-free tiling that `Constrained::layout` gets from `discretise_axis`.
+        let mut edges = Vec::with_capacity(n + 1);
+        edges.push(0u32);
+        for &b in &self.boundaries {
+            let edge = (b * axis_len as f32).round() as u32;
+            // Guard against adjacent boundaries rounding to the same (or an out-of-order)
+            // pixel on a very small axis, which would otherwise underflow the `len` below.
+            edges.push(edge.max(*edges.last().unwrap()));
+        }
+        edges.push(axis_len.max(*edges.last().unwrap()));
+
+        let positions = ids
+            .into_iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let offset = edges[i];
+                let len = edges[i + 1] - edges[i];
+
+                let rect = match self.direction {
+                    SplitDirection::Horizontal => Rect::new(r.x + offset, r.y, len, r.h),
+                    SplitDirection::Vertical => Rect::new(r.x, r.y + offset, r.w, len),
+                };
+
+                (id, rect)
+            })
+            .collect();
+
+        (None, positions)
+    }
+
+    fn handle_message(&mut self, m: &Message) -> Option<Box<dyn Layout>> {
+        if let Some(&Grow(target)) = m.downcast_ref() {
+            self.resize_focused(target, true);
+        } else if let Some(&Shrink(target)) = m.downcast_ref() {
+            self.resize_focused(target, false);
+        }
+
+        None
+    }
+}
@@ -7,8 +7,12 @@ use crate::{
 };
 
 mod combinators;
+mod grid;
+mod parse;
 
-pub use combinators::Conditional;
+pub use combinators::{Conditional, SplitChild, SplitLayout};
+pub use grid::{Grow, ResizableGrid, ResizeTarget, Shrink};
+pub use parse::{parse_layout, LayoutParseError, Span};
 
 // NOTE: When adding new layouts to this module, they should have a corresponding quickcheck
 //       test added to ensure that the layout logic does not panic when given arbitrary inputs.
@@ -140,6 +144,274 @@ impl Layout for Fibonacci {
     }
 }
 
+/// The axis that a [Rect] is being divided along.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// Split left to right, producing a column per entry.
+    Horizontal,
+    /// Split top to bottom, producing a row per entry.
+    Vertical,
+}
+
+/// A single entry in the set of constraints used by [Constrained].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SplitSize {
+    /// An exact number of pixels.
+    Fixed(u32),
+    /// A proportion of whatever space remains once all [SplitSize::Fixed] entries have
+    /// been subtracted from the available axis length.
+    Percent(f32),
+}
+
+/// Discretise `sizes` into integer pixel lengths that sum to exactly `total`.
+///
+/// [SplitSize::Fixed] entries are taken verbatim, clamped cumulatively against whatever of
+/// `total` is still unspent so that a run of oversized entries can't overflow the region
+/// between them. The remaining space
+/// is shared between [SplitSize::Percent] entries in proportion to their weight, using the
+/// "largest remainder" method: each entry's ideal floating-point size is floored, and then
+/// the pixels lost to flooring are handed out one at a time to the entries with the largest
+/// fractional remainder. If there are no [SplitSize::Percent] entries left to soak up the
+/// rounding (e.g. the constraints are all [SplitSize::Fixed] and undershoot `total`), any
+/// pixels still unaccounted for are handed to the last entry instead. This guarantees the
+/// returned lengths always sum to `total` exactly, regardless of how the rounding falls.
+fn discretise_axis(sizes: &[SplitSize], total: u32) -> Vec<u32> {
+    if sizes.is_empty() {
+        return Vec::new();
+    }
+
+    // Clamp each Fixed entry against whatever budget remains at that point, rather than
+    // against the raw `total`, so that a run of oversized Fixed entries can't individually
+    // "fit" while their sum still overflows the region.
+    let mut out = vec![0u32; sizes.len()];
+    let mut budget = total;
+    for (i, s) in sizes.iter().enumerate() {
+        if let SplitSize::Fixed(px) = s {
+            out[i] = (*px).min(budget);
+            budget -= out[i];
+        }
+    }
+    let remaining = budget;
+
+    let weight_sum: f32 = sizes
+        .iter()
+        .filter_map(|s| match s {
+            SplitSize::Percent(p) => Some(p.max(0.0)),
+            SplitSize::Fixed(_) => None,
+        })
+        .sum();
+
+    let mut remainders = Vec::new();
+
+    for (i, s) in sizes.iter().enumerate() {
+        match s {
+            SplitSize::Fixed(_) => {}
+            SplitSize::Percent(p) => {
+                let ideal = if weight_sum > 0.0 {
+                    remaining as f32 * (p.max(0.0) / weight_sum)
+                } else {
+                    0.0
+                };
+                out[i] = ideal.floor() as u32;
+                remainders.push((i, ideal.fract()));
+            }
+        }
+    }
+
+    remainders.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    let mut leftover = total.saturating_sub(out.iter().sum());
+    for (i, _) in remainders {
+        if leftover == 0 {
+            break;
+        }
+        out[i] += 1;
+        leftover -= 1;
+    }
+
+    // There was nothing (or nothing left) to soak up rounding with a `Percent` entry, e.g.
+    // a constraint list made up entirely of `Fixed` entries that undershoots `total`. Hand
+    // whatever remains to the last entry so the result still sums to `total` exactly.
+    if leftover > 0 {
+        if let Some(last) = out.last_mut() {
+            *last += leftover;
+        }
+    }
+
+    out
+}
+
+/// A layout that positions clients along a single axis according to a set of [SplitSize]
+/// constraints, rather than a single fixed ratio.
+///
+/// This is the sizing model used by parametric tiling terminal layouts: each client is
+/// given either a fixed pixel size or a percentage share of whatever space is left after
+/// the fixed entries have been accounted for. If there are more clients than constraints,
+/// the final constraint is reused for each additional client. If there are fewer clients
+/// than constraints, only the leading constraints needed to cover the clients present are
+/// used (and their percentages are renormalised across that smaller set).
+///
+/// [ExpandMain] and [ShrinkMain] nudge the first [SplitSize::Percent] entry up or down by a
+/// configurable step, taking the change out of (or giving it back to) the other percentage
+/// entries so that they continue to renormalise to the same remaining space. Percentages
+/// are clamped so that no percentage entry is pushed below `min_percent`.
+#[derive(Debug, Clone)]
+pub struct Constrained {
+    direction: SplitDirection,
+    constraints: Vec<SplitSize>,
+    step: f32,
+    min_percent: f32,
+}
+
+impl Default for Constrained {
+    fn default() -> Self {
+        Self {
+            direction: SplitDirection::Horizontal,
+            constraints: vec![SplitSize::Percent(1.0)],
+            step: 0.05,
+            min_percent: 0.05,
+        }
+    }
+}
+
+impl Constrained {
+    /// Create a new [Constrained] layout that splits along `direction` using `constraints`.
+    pub fn new(direction: SplitDirection, constraints: Vec<SplitSize>) -> Self {
+        Self {
+            direction,
+            constraints,
+            ..Self::default()
+        }
+    }
+
+    /// Create a new [Constrained] layout as with `new` but returned as a trait object ready
+    /// to be added to your layout stack.
+    pub fn boxed(direction: SplitDirection, constraints: Vec<SplitSize>) -> Box<dyn Layout> {
+        Box::new(Constrained::new(direction, constraints))
+    }
+
+    /// Set the amount that [ExpandMain] and [ShrinkMain] move the first [SplitSize::Percent]
+    /// entry by, and the minimum that any percentage entry can be pushed down to.
+    pub fn with_step(mut self, step: f32, min_percent: f32) -> Self {
+        self.step = step;
+        self.min_percent = min_percent;
+        self
+    }
+
+    fn sizes_for(&self, n: usize) -> Vec<SplitSize> {
+        if self.constraints.is_empty() {
+            return vec![SplitSize::Percent(1.0 / n as f32); n];
+        }
+
+        (0..n)
+            .map(|i| {
+                self.constraints
+                    .get(i)
+                    .copied()
+                    .unwrap_or_else(|| *self.constraints.last().unwrap())
+            })
+            .collect()
+    }
+
+    fn nudge_first_percent(&mut self, delta: f32) {
+        let Some(idx) = self
+            .constraints
+            .iter()
+            .position(|s| matches!(s, SplitSize::Percent(_)))
+        else {
+            return;
+        };
+
+        let others: Vec<usize> = self
+            .constraints
+            .iter()
+            .enumerate()
+            .filter(|&(i, s)| i != idx && matches!(s, SplitSize::Percent(_)))
+            .map(|(i, _)| i)
+            .collect();
+
+        if others.is_empty() {
+            return;
+        }
+
+        let SplitSize::Percent(p) = self.constraints[idx] else {
+            unreachable!("idx is known to be a Percent entry")
+        };
+        let max_p = 1.0 - self.min_percent * others.len() as f32;
+        let new_p = (p + delta).clamp(self.min_percent, max_p.max(self.min_percent));
+        let taken = new_p - p;
+
+        let others_total: f32 = others
+            .iter()
+            .map(|&i| match self.constraints[i] {
+                SplitSize::Percent(op) => op,
+                SplitSize::Fixed(_) => 0.0,
+            })
+            .sum();
+
+        if others_total > 0.0 {
+            for &i in &others {
+                if let SplitSize::Percent(op) = &mut self.constraints[i] {
+                    let share = *op / others_total;
+                    *op = (*op - taken * share).max(self.min_percent);
+                }
+            }
+        }
+
+        self.constraints[idx] = SplitSize::Percent(new_p);
+    }
+}
+
+impl Layout for Constrained {
+    fn name(&self) -> String {
+        "Constrained".to_string()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Layout> {
+        Box::new(self.clone())
+    }
+
+    fn layout(&mut self, s: &Stack<Xid>, r: Rect) -> (Option<Box<dyn Layout>>, Vec<(Xid, Rect)>) {
+        let n = s.len();
+        if n == 0 {
+            return (None, vec![]);
+        }
+
+        let sizes = self.sizes_for(n);
+        let axis_len = match self.direction {
+            SplitDirection::Horizontal => r.w,
+            SplitDirection::Vertical => r.h,
+        };
+        let lengths = discretise_axis(&sizes, axis_len);
+
+        let mut offset = 0;
+        let positions = s
+            .iter()
+            .zip(lengths)
+            .map(|(&id, len)| {
+                let rect = match self.direction {
+                    SplitDirection::Horizontal => Rect::new(r.x + offset, r.y, len, r.h),
+                    SplitDirection::Vertical => Rect::new(r.x, r.y + offset, r.w, len),
+                };
+                offset += len;
+
+                (id, rect)
+            })
+            .collect();
+
+        (None, positions)
+    }
+
+    fn handle_message(&mut self, m: &Message) -> Option<Box<dyn Layout>> {
+        if let Some(&ExpandMain) = m.downcast_ref() {
+            self.nudge_first_percent(self.step);
+        } else if let Some(&ShrinkMain) = m.downcast_ref() {
+            self.nudge_first_percent(-self.step);
+        }
+
+        None
+    }
+}
+
 /// Inspired by the Tatami layout available for dwm:
 ///   <https://dwm.suckless.org/patches/tatami/>
 ///
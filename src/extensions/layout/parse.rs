@@ -0,0 +1,457 @@
+//! A small parser for describing layouts declaratively as nested, KDL-style text, so that
+//! layouts can be reloaded from a user's config without recompiling.
+//!
+//! ```text
+//! split direction=horizontal {
+//!     fibonacci size=50% ratio=0.5 cutoff=40 ratio_step=0.1
+//!     split size=50% direction=vertical {
+//!         tatami size=60% ratio=0.6 ratio_step=0.1
+//!         constrained size=40% sizes="50%,50%"
+//!     }
+//! }
+//! ```
+use crate::{
+    core::layout::Layout,
+    extensions::layout::{
+        Constrained, Fibonacci, ResizableGrid, SplitChild, SplitDirection, SplitLayout, SplitSize,
+        Tatami,
+    },
+};
+use std::fmt;
+
+/// The byte range in the original input that a [LayoutParseError] applies to, so that a
+/// caller can point a user at the exact offending text rather than just a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Byte offset of the first character covered by this span.
+    pub start: usize,
+    /// Byte offset one past the last character covered by this span.
+    pub end: usize,
+}
+
+impl Span {
+    fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+/// Everything that can go wrong while parsing a layout description with [parse_layout].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutParseError {
+    /// A leaf node named a layout that this parser does not know how to build.
+    UnknownLayout { name: String, span: Span },
+    /// A `split` node's children had `size` attributes that did not sum to 100%.
+    SizesDontSumTo100 { sum: f32, span: Span },
+    /// The input could not be parsed as a well-formed node tree.
+    Malformed { message: String, span: Span },
+}
+
+impl fmt::Display for LayoutParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutParseError::UnknownLayout { name, span } => {
+                write!(f, "unknown layout '{name}' at {}..{}", span.start, span.end)
+            }
+            LayoutParseError::SizesDontSumTo100 { sum, span } => {
+                write!(
+                    f,
+                    "split sizes must sum to 100%, got {:.1}% at {}..{}",
+                    sum * 100.0,
+                    span.start,
+                    span.end
+                )
+            }
+            LayoutParseError::Malformed { message, span } => {
+                write!(f, "{message} at {}..{}", span.start, span.end)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident,
+    LBrace,
+    RBrace,
+    Eq,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    text: String,
+    span: Span,
+}
+
+fn lex(input: &str) -> Vec<Token> {
+    // Walk `char_indices` rather than raw bytes: every `pos` here is a real char boundary,
+    // so slicing `input[a..b]` below can never land inside a multi-byte UTF-8 sequence.
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let len = input.len();
+    let byte_at = |i: usize| chars.get(i).map(|&(pos, _)| pos).unwrap_or(len);
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (pos, c) = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1).map(|&(_, c)| c) == Some('/') {
+            while i < chars.len() && chars[i].1 != '\n' {
+                i += 1;
+            }
+        } else if c == '{' {
+            tokens.push(Token {
+                kind: TokenKind::LBrace,
+                text: "{".to_string(),
+                span: Span::new(pos, byte_at(i + 1)),
+            });
+            i += 1;
+        } else if c == '}' {
+            tokens.push(Token {
+                kind: TokenKind::RBrace,
+                text: "}".to_string(),
+                span: Span::new(pos, byte_at(i + 1)),
+            });
+            i += 1;
+        } else if c == '=' {
+            tokens.push(Token {
+                kind: TokenKind::Eq,
+                text: "=".to_string(),
+                span: Span::new(pos, byte_at(i + 1)),
+            });
+            i += 1;
+        } else if c == '"' {
+            let start = pos;
+            i += 1;
+            let text_start = byte_at(i);
+            while i < chars.len() && chars[i].1 != '"' {
+                i += 1;
+            }
+            let text_end = byte_at(i);
+            i = (i + 1).min(chars.len());
+            tokens.push(Token {
+                kind: TokenKind::Ident,
+                text: input[text_start..text_end].to_string(),
+                span: Span::new(start, byte_at(i)),
+            });
+        } else {
+            let start = pos;
+            while i < chars.len() {
+                let c = chars[i].1;
+                if c.is_whitespace() || "{}=\"".contains(c) {
+                    break;
+                }
+                i += 1;
+            }
+            tokens.push(Token {
+                kind: TokenKind::Ident,
+                text: input[start..byte_at(i)].to_string(),
+                span: Span::new(start, byte_at(i)),
+            });
+        }
+    }
+
+    tokens
+}
+
+struct Node {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+    span: Span,
+}
+
+impl Node {
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn eof_span(&self) -> Span {
+        self.tokens
+            .last()
+            .map(|t| Span::new(t.span.end, t.span.end))
+            .unwrap_or(Span::new(0, 0))
+    }
+
+    fn parse_node(&mut self) -> Result<Node, LayoutParseError> {
+        let name_tok = self.next().ok_or_else(|| LayoutParseError::Malformed {
+            message: "expected a layout name".to_string(),
+            span: self.eof_span(),
+        })?;
+        if name_tok.kind != TokenKind::Ident {
+            return Err(LayoutParseError::Malformed {
+                message: format!("expected a layout name, found '{}'", name_tok.text),
+                span: name_tok.span,
+            });
+        }
+
+        let start = name_tok.span.start;
+        let mut attrs = Vec::new();
+
+        while let Some(tok) = self.peek() {
+            match tok.kind {
+                TokenKind::Ident if !tok.text.is_empty() => {
+                    // Lookahead for `key=value`: if the token after this one isn't `=`,
+                    // this identifier belongs to the next node instead.
+                    if self.tokens.get(self.pos + 1).map(|t| &t.kind) != Some(&TokenKind::Eq) {
+                        break;
+                    }
+                    let key = self.next().unwrap().text;
+                    self.next(); // `=`
+                    let value = self.next().ok_or_else(|| LayoutParseError::Malformed {
+                        message: format!("expected a value for '{key}'"),
+                        span: self.eof_span(),
+                    })?;
+                    attrs.push((key, value.text));
+                }
+                _ => break,
+            }
+        }
+
+        let mut children = Vec::new();
+        let mut end = self
+            .tokens
+            .get(self.pos.saturating_sub(1))
+            .map(|t| t.span.end)
+            .unwrap_or(start);
+
+        if let Some(tok) = self.peek() {
+            if tok.kind == TokenKind::LBrace {
+                self.next();
+                while let Some(tok) = self.peek() {
+                    if tok.kind == TokenKind::RBrace {
+                        break;
+                    }
+                    children.push(self.parse_node()?);
+                }
+                let close = self.next().ok_or_else(|| LayoutParseError::Malformed {
+                    message: "unterminated '{'".to_string(),
+                    span: Span::new(start, self.eof_span().end),
+                })?;
+                if close.kind != TokenKind::RBrace {
+                    return Err(LayoutParseError::Malformed {
+                        message: "expected '}'".to_string(),
+                        span: close.span,
+                    });
+                }
+                end = close.span.end;
+            }
+        }
+
+        Ok(Node {
+            name: name_tok.text,
+            attrs,
+            children,
+            span: Span::new(start, end),
+        })
+    }
+}
+
+fn parse_f32(node: &Node, key: &str, default: f32) -> Result<f32, LayoutParseError> {
+    match node.attr(key) {
+        None => Ok(default),
+        Some(v) => v.trim_end_matches('%').parse().map_err(|_| LayoutParseError::Malformed {
+            message: format!("'{key}' must be a number, got '{v}'"),
+            span: node.span,
+        }),
+    }
+}
+
+fn parse_u32(node: &Node, key: &str, default: u32) -> Result<u32, LayoutParseError> {
+    match node.attr(key) {
+        None => Ok(default),
+        Some(v) => v.parse().map_err(|_| LayoutParseError::Malformed {
+            message: format!("'{key}' must be a whole number, got '{v}'"),
+            span: node.span,
+        }),
+    }
+}
+
+fn parse_direction(node: &Node, default: SplitDirection) -> Result<SplitDirection, LayoutParseError> {
+    match node.attr("direction") {
+        None => Ok(default),
+        Some("horizontal") => Ok(SplitDirection::Horizontal),
+        Some("vertical") => Ok(SplitDirection::Vertical),
+        Some(other) => Err(LayoutParseError::Malformed {
+            message: format!("'direction' must be 'horizontal' or 'vertical', got '{other}'"),
+            span: node.span,
+        }),
+    }
+}
+
+fn parse_split_size(raw: &str, span: Span) -> Result<SplitSize, LayoutParseError> {
+    if let Some(pct) = raw.strip_suffix('%') {
+        let p: f32 = pct.parse().map_err(|_| LayoutParseError::Malformed {
+            message: format!("'{raw}' is not a valid percentage"),
+            span,
+        })?;
+        Ok(SplitSize::Percent(p / 100.0))
+    } else {
+        let px: u32 = raw
+            .trim_end_matches("px")
+            .parse()
+            .map_err(|_| LayoutParseError::Malformed {
+                message: format!("'{raw}' is not a valid fixed size"),
+                span,
+            })?;
+        Ok(SplitSize::Fixed(px))
+    }
+}
+
+fn parse_sizes_list(node: &Node) -> Result<Vec<SplitSize>, LayoutParseError> {
+    match node.attr("sizes") {
+        None => Ok(Vec::new()),
+        Some(list) => list
+            .split(',')
+            .map(|s| parse_split_size(s.trim(), node.span))
+            .collect(),
+    }
+}
+
+fn node_size_percent(node: &Node) -> Result<f32, LayoutParseError> {
+    match node.attr("size") {
+        Some(raw) => match parse_split_size(raw, node.span)? {
+            SplitSize::Percent(p) => Ok(p),
+            SplitSize::Fixed(_) => Err(LayoutParseError::Malformed {
+                message: "children of a 'split' must use a percent 'size', not a fixed one"
+                    .to_string(),
+                span: node.span,
+            }),
+        },
+        None => Err(LayoutParseError::Malformed {
+            message: "children of a 'split' must specify a percent 'size'".to_string(),
+            span: node.span,
+        }),
+    }
+}
+
+fn fold_split(direction: SplitDirection, mut children: Vec<(Box<dyn Layout>, f32)>) -> Box<dyn Layout> {
+    let (mut acc_layout, mut acc_percent) = children.pop().expect("at least one child");
+
+    while let Some((layout, percent)) = children.pop() {
+        let total = percent + acc_percent;
+        let ratio = percent / total;
+        let first = SplitChild::new(layout, ((percent * 100.0).round() as u32).max(1));
+        let second = SplitChild::new(acc_layout, ((acc_percent * 100.0).round() as u32).max(1));
+        acc_layout = SplitLayout::boxed(direction, ratio, first, second);
+        acc_percent = total;
+    }
+
+    acc_layout
+}
+
+fn build(node: &Node) -> Result<Box<dyn Layout>, LayoutParseError> {
+    match node.name.as_str() {
+        "split" => {
+            let direction = parse_direction(node, SplitDirection::Horizontal)?;
+            if node.children.len() < 2 {
+                return Err(LayoutParseError::Malformed {
+                    message: "'split' needs at least two children".to_string(),
+                    span: node.span,
+                });
+            }
+
+            let mut children = Vec::with_capacity(node.children.len());
+            let mut sum = 0.0;
+            for child in &node.children {
+                let percent = node_size_percent(child)?;
+                sum += percent;
+                children.push((build(child)?, percent));
+            }
+
+            if (sum - 1.0).abs() > 0.01 {
+                return Err(LayoutParseError::SizesDontSumTo100 {
+                    sum,
+                    span: node.span,
+                });
+            }
+
+            Ok(fold_split(direction, children))
+        }
+
+        "fibonacci" => {
+            let ratio = parse_f32(node, "ratio", 0.5)?;
+            let cutoff = parse_u32(node, "cutoff", 40)?;
+            let ratio_step = parse_f32(node, "ratio_step", 0.1)?;
+            Ok(Fibonacci::boxed(cutoff, ratio, ratio_step))
+        }
+
+        "tatami" => {
+            let ratio = parse_f32(node, "ratio", 0.6)?;
+            let ratio_step = parse_f32(node, "ratio_step", 0.1)?;
+            Ok(Tatami::boxed(ratio, ratio_step))
+        }
+
+        "constrained" => {
+            let direction = parse_direction(node, SplitDirection::Horizontal)?;
+            let sizes = parse_sizes_list(node)?;
+            Ok(Constrained::boxed(direction, sizes))
+        }
+
+        "resizable_grid" => {
+            let direction = parse_direction(node, SplitDirection::Horizontal)?;
+            Ok(ResizableGrid::boxed(direction))
+        }
+
+        other => Err(LayoutParseError::UnknownLayout {
+            name: other.to_string(),
+            span: node.span,
+        }),
+    }
+}
+
+/// Parse a declarative, KDL-style layout description into a composed [Layout], built from
+/// the [SplitLayout] combinator and the builtin [Fibonacci], [Tatami], [Constrained] and
+/// [ResizableGrid] layouts.
+///
+/// A description is a single root node, optionally followed by `{ ... }` children for
+/// `split` nodes. Every node may carry `key=value` attributes, e.g. `fibonacci ratio=0.5`.
+/// A `split` node takes a `direction` of `horizontal` or `vertical` (defaulting to
+/// `horizontal` when omitted) and requires at least two children, each of which must specify what percentage of the split it occupies via
+/// `size=NN%`; the children's sizes must sum to 100%.
+///
+/// Errors report the offending byte span within `input` so that a caller can point a user
+/// at exactly what needs fixing rather than just a generic message.
+pub fn parse_layout(input: &str) -> Result<Box<dyn Layout>, LayoutParseError> {
+    let tokens = lex(input);
+    let mut parser = Parser::new(tokens);
+    let node = parser.parse_node()?;
+
+    if let Some(tok) = parser.peek() {
+        return Err(LayoutParseError::Malformed {
+            message: format!("unexpected trailing input '{}'", tok.text),
+            span: tok.span,
+        });
+    }
+
+    build(&node)
+}